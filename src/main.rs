@@ -3,7 +3,7 @@ use std::cmp::Ordering;
 use std::collections::{BinaryHeap, HashMap};
 use std::env;
 use std::fs::File;
-use std::io::{stdout, BufRead, BufReader, BufWriter, Read, Write};
+use std::io::{stdout, BufRead, BufReader, BufWriter, Write};
 use std::path::Path;
 
 use clap::{Parser, Subcommand};
@@ -15,23 +15,121 @@ struct CharPosition {
     position: usize,
 }
 
+// Clue is the per-letter Wordle feedback: green, yellow, or gray.
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash)]
+enum Clue {
+    Green,
+    Yellow,
+    Gray,
+}
+
+// compute_pattern returns the Wordle feedback for `guess` against `answer`.
+// Repeated letters are handled in two passes: greens consume their answer
+// letter first, then yellows only claim a letter still unconsumed.
+fn compute_pattern(guess: &str, answer: &str) -> Vec<Clue> {
+    let guess_chars: Vec<char> = guess.chars().collect();
+    let answer_chars: Vec<char> = answer.chars().collect();
+    let len = guess_chars.len();
+
+    let mut pattern = vec![Clue::Gray; len];
+    let mut consumed = vec![false; len];
+
+    for i in 0..len {
+        if guess_chars[i] == answer_chars[i] {
+            pattern[i] = Clue::Green;
+            consumed[i] = true;
+        }
+    }
+
+    for i in 0..len {
+        if pattern[i] == Clue::Green {
+            continue;
+        }
+        if let Some(j) = (0..len).find(|&j| !consumed[j] && answer_chars[j] == guess_chars[i]) {
+            pattern[i] = Clue::Yellow;
+            consumed[j] = true;
+        }
+    }
+
+    pattern
+}
+
+// pattern_buckets groups `candidates` by the feedback pattern `guess` produces
+// against each of them, returning the bucket sizes.
+fn pattern_buckets(guess: &str, candidates: &[String]) -> HashMap<Vec<Clue>, usize> {
+    let mut buckets = HashMap::new();
+    for answer in candidates {
+        *buckets.entry(compute_pattern(guess, answer)).or_insert(0) += 1;
+    }
+    buckets
+}
+
+// entropy is the expected information gain in bits over `guess`'s
+// feedback-pattern buckets: E(g) = -Σ (c_p/N)·log2(c_p/N).
+fn entropy(buckets: &HashMap<Vec<Clue>, usize>, n: f64) -> f64 {
+    buckets
+        .values()
+        .map(|&c| {
+            let p = c as f64 / n;
+            -p * p.log2()
+        })
+        .sum()
+}
+
+// worst_case_bucket is the size of the largest feedback-pattern bucket: the
+// worst-case remaining count, as opposed to entropy's average case. Smaller
+// is better.
+fn worst_case_bucket(buckets: &HashMap<Vec<Clue>, usize>) -> usize {
+    buckets.values().copied().max().unwrap_or(0)
+}
+
+// expected_remaining is Σ c_p²/N over the feedback-pattern buckets: the
+// expected number of candidates left after guessing.
+fn expected_remaining(buckets: &HashMap<Vec<Clue>, usize>, n: f64) -> f64 {
+    buckets.values().map(|&c| (c * c) as f64 / n).sum()
+}
+
+// char_index maps 'a'..='z' (case-insensitive) to 0..26 for the per-letter count tables.
+#[inline]
+fn char_index(c: char) -> usize {
+    c.to_ascii_lowercase() as usize - 'a' as usize
+}
+
 #[derive(Debug)]
 struct Filter {
     length: usize,
-    ignore_chars: Vec<char>,
+    // min_counts[c] is the fewest occurrences of letter c a candidate must have.
+    min_counts: [usize; 26],
+    // max_counts[c] caps occurrences of letter c; a gray clue caps it at the
+    // count already required by green/yellow clues (zero if none) rather
+    // than banning the letter outright.
+    max_counts: [Option<usize>; 26],
     char_positions: Vec<CharPosition>,
     different_char_positions: Vec<CharPosition>,
 }
 
 impl Filter {
     fn new(
+        length: usize,
         ignore_chars: Vec<char>,
         char_positions: Vec<CharPosition>,
         different_char_positions: Vec<CharPosition>,
     ) -> Self {
+        let mut min_counts = [0usize; 26];
+        for cp in char_positions.iter().chain(different_char_positions.iter()) {
+            min_counts[char_index(cp.char)] += 1;
+        }
+
+        let mut max_counts: [Option<usize>; 26] = [None; 26];
+        for c in ignore_chars.iter() {
+            let idx = char_index(*c);
+            max_counts[idx] = Some(min_counts[idx]);
+        }
+
         Self {
-            length: 5,
-            ignore_chars,
+            length,
+            min_counts,
+            max_counts,
             char_positions,
             different_char_positions,
         }
@@ -42,12 +140,6 @@ impl Filter {
             return false;
         }
 
-        for c in self.ignore_chars.iter() {
-            if word.find(*c).is_some() {
-                return false;
-            }
-        }
-
         if !self.accept_char_position(word) {
             return false;
         }
@@ -78,25 +170,221 @@ impl Filter {
         true
     }
 
+    // accept_char checks each letter's count in `word` against min_counts/max_counts.
     fn accept_char(&self, word: &str) -> bool {
-        for cp in self.different_char_positions.iter() {
-            if word.find(cp.char).is_none() {
+        let mut counts = [0usize; 26];
+        for c in word.chars().filter(|c| c.is_ascii_alphabetic()) {
+            counts[char_index(c)] += 1;
+        }
+
+        for (i, &count) in counts.iter().enumerate() {
+            if count < self.min_counts[i] {
                 return false;
             }
+            if let Some(max) = self.max_counts[i] {
+                if count > max {
+                    return false;
+                }
+            }
         }
+
         true
     }
 }
 
+// WordIndex is a precomputed inverted index over a same-length word list:
+// per letter, the word indices containing it, and per (letter, position)
+// pair, the word indices with that letter at that position. `query` turns a
+// `Filter`'s clues into set algebra over these bitsets, but can't express
+// occurrence-count bounds, so callers still run `Filter::accept` on the result.
+#[derive(Debug)]
+struct WordIndex {
+    length: usize,
+    words: Vec<String>,
+    contains: Vec<BitSet>,
+    positions: Vec<Vec<BitSet>>,
+}
+
+impl WordIndex {
+    fn build_from_dict<P: AsRef<Path>>(dict_path: P, length: usize) -> std::io::Result<Self> {
+        let file = File::open(dict_path)?;
+        let lines = BufReader::new(file).lines();
+
+        let mut words = Vec::new();
+        let mut contains: Vec<BitSet> = (0..26).map(|_| BitSet::new()).collect();
+        let mut positions: Vec<Vec<BitSet>> = (0..26)
+            .map(|_| (0..length).map(|_| BitSet::new()).collect())
+            .collect();
+
+        for line in lines {
+            match line {
+                Ok(line) if line.len() == length => {
+                    // char_index lowercases per-character, so the bitset keys
+                    // are case-insensitive without lowercasing the stored word.
+                    let idx = words.len();
+                    for (pos, c) in line.char_indices() {
+                        if !c.is_ascii_alphabetic() {
+                            continue;
+                        }
+                        let ci = char_index(c);
+                        contains[ci].insert(idx);
+                        positions[ci][pos].insert(idx);
+                    }
+                    words.push(line);
+                }
+                _ => continue,
+            }
+        }
+
+        Ok(Self {
+            length,
+            words,
+            contains,
+            positions,
+        })
+    }
+
+    // query narrows the dictionary down to the word indices consistent with
+    // `filter`'s position and presence clues.
+    fn query(&self, filter: &Filter) -> BitSet {
+        let mut result = BitSet::with_capacity(self.words.len());
+        for i in 0..self.words.len() {
+            result.insert(i);
+        }
+
+        for cp in filter.char_positions.iter() {
+            result.intersect_with(&self.positions[char_index(cp.char)][cp.position]);
+        }
+        for cp in filter.different_char_positions.iter() {
+            let ci = char_index(cp.char);
+            result.intersect_with(&self.contains[ci]);
+            result.difference_with(&self.positions[ci][cp.position]);
+        }
+        for (i, max) in filter.max_counts.iter().enumerate() {
+            if *max == Some(0) {
+                result.difference_with(&self.contains[i]);
+            }
+        }
+
+        result
+    }
+
+    fn save<P: AsRef<Path>>(&self, path: P) -> std::io::Result<()> {
+        let out = File::options()
+            .create(true)
+            .truncate(true)
+            .write(true)
+            .open(path)?;
+        let mut out = BufWriter::new(out);
+
+        writeln!(out, "{}", self.length)?;
+        writeln!(out, "{}", self.words.len())?;
+        for word in self.words.iter() {
+            writeln!(out, "{}", word)?;
+        }
+        for bs in self.contains.iter() {
+            writeln!(out, "{}", bitset_to_line(bs))?;
+        }
+        for per_letter in self.positions.iter() {
+            for bs in per_letter.iter() {
+                writeln!(out, "{}", bitset_to_line(bs))?;
+            }
+        }
+
+        Ok(())
+    }
+
+    fn load<P: AsRef<Path>>(path: P) -> std::io::Result<Self> {
+        let file = File::open(path)?;
+        let mut lines = BufReader::new(file).lines();
+
+        let length = lines.next().ok_or_else(eof)??.parse::<usize>().map_err(invalid)?;
+        let word_count = lines.next().ok_or_else(eof)??.parse::<usize>().map_err(invalid)?;
+
+        let mut words = Vec::with_capacity(word_count);
+        for _ in 0..word_count {
+            words.push(lines.next().ok_or_else(eof)??);
+        }
+
+        let mut contains = Vec::with_capacity(26);
+        for _ in 0..26 {
+            contains.push(line_to_bitset(lines.next().ok_or_else(eof)??.as_str()));
+        }
+
+        let mut positions = Vec::with_capacity(26);
+        for _ in 0..26 {
+            let mut per_letter = Vec::with_capacity(length);
+            for _ in 0..length {
+                per_letter.push(line_to_bitset(lines.next().ok_or_else(eof)??.as_str()));
+            }
+            positions.push(per_letter);
+        }
+
+        Ok(Self {
+            length,
+            words,
+            contains,
+            positions,
+        })
+    }
+}
+
+fn eof() -> std::io::Error {
+    std::io::Error::new(std::io::ErrorKind::UnexpectedEof, "index file ended early")
+}
+
+fn invalid(e: std::num::ParseIntError) -> std::io::Error {
+    std::io::Error::new(std::io::ErrorKind::InvalidData, e)
+}
+
+fn bitset_to_line(bs: &BitSet) -> String {
+    bs.iter()
+        .map(|i| i.to_string())
+        .collect::<Vec<_>>()
+        .join(",")
+}
+
+fn line_to_bitset(line: &str) -> BitSet {
+    let mut bs = BitSet::new();
+    for part in line.split(',') {
+        if let Ok(i) = part.parse::<usize>() {
+            bs.insert(i);
+        }
+    }
+    bs
+}
+
 #[cfg(test)]
 mod tests {
-    use crate::{CharFreq, CharPosition, Filter, WordScore};
+    use crate::{
+        compute_pattern, entropy, expected_remaining, pattern_buckets, worst_case_bucket,
+        CharFreq, CharPosition, Clue, Filter, WordIndex, WordScore,
+    };
     use std::collections::BinaryHeap;
+    use std::io::Write;
+    use std::path::PathBuf;
+
+    // write_temp_dict writes `words` one per line to a fresh temp file and
+    // returns its path, for tests that need WordIndex::build_from_dict to
+    // read from disk.
+    fn write_temp_dict(tag: &str, words: &[&str]) -> PathBuf {
+        let path = std::env::temp_dir().join(format!(
+            "wordle_test_{}_{}_{:?}.dict",
+            tag,
+            std::process::id(),
+            std::thread::current().id()
+        ));
+        let mut file = std::fs::File::create(&path).unwrap();
+        for word in words {
+            writeln!(file, "{}", word).unwrap();
+        }
+        path
+    }
 
     #[test]
     fn filter_test() {
         let ignores = vec!['a', 'b', 'c'];
-        let filter = Filter::new(ignores, vec![], vec![]);
+        let filter = Filter::new(5, ignores, vec![], vec![]);
 
         assert!(!filter.accept("word"));
         assert!(!filter.accept("audio"));
@@ -113,7 +401,7 @@ mod tests {
                 position: 4,
             },
         ];
-        let filter = Filter::new(ignores, char_positions, vec![]);
+        let filter = Filter::new(5, ignores, char_positions, vec![]);
         assert!(!filter.accept("avoid"));
         assert!(!filter.accept("wheel"));
         assert!(!filter.accept("false"));
@@ -135,7 +423,7 @@ mod tests {
             char: 'r',
             position: 1,
         }];
-        let filter = Filter::new(ignores, char_positions, different_char_positions);
+        let filter = Filter::new(5, ignores, char_positions, different_char_positions);
         assert!(!filter.accept("avoid"));
         assert!(!filter.accept("wheel"));
         assert!(!filter.accept("false"));
@@ -145,6 +433,23 @@ mod tests {
         assert!(filter.accept("doree"));
     }
 
+    #[test]
+    fn filter_duplicate_letters_test() {
+        // guessing "geese" against "abbey" yields gray-g, yellow-e@1,
+        // gray-e@2, gray-s, gray-e@4: the 'e' is gray in two spots but
+        // yellow in a third, so it means "exactly one e", not "no e".
+        let ignores = vec!['g', 'e', 's'];
+        let different_char_positions = vec![CharPosition {
+            char: 'e',
+            position: 1,
+        }];
+        let filter = Filter::new(5, ignores, vec![], different_char_positions);
+
+        assert!(filter.accept("abbey"));
+        assert!(!filter.accept("emcee")); // three e's, exceeds the max of one
+        assert!(!filter.accept("mammy")); // no e at all, below the min of one
+    }
+
     #[test]
     fn char_freq_test() {
         let mut char_freq = CharFreq::new();
@@ -204,14 +509,197 @@ mod tests {
 
         assert_eq!(heap.pop(), None);
     }
-}
 
-fn parse_char_position(target: String) -> Vec<CharPosition> {
-    let mut ret = Vec::new();
+    #[test]
+    fn compute_pattern_test() {
+        use Clue::*;
+
+        // no repeated letters, every letter shifted to a different
+        // position: straightforward all-yellow.
+        assert_eq!(
+            compute_pattern("abcde", "bcdea"),
+            vec![Yellow, Yellow, Yellow, Yellow, Yellow]
+        );
+        assert_eq!(
+            compute_pattern("crate", "crate"),
+            vec![Green, Green, Green, Green, Green]
+        );
+
+        // guess has a repeated letter, answer has only one: the first
+        // occurrence (scanned left to right) consumes the match, later
+        // occurrences are gray.
+        assert_eq!(
+            compute_pattern("geese", "abbey"),
+            vec![Gray, Yellow, Gray, Gray, Gray]
+        );
+
+        // guess has a repeated letter, answer has it once, in the spot of
+        // one occurrence: that 'e' is green and the other two are gray
+        // (the yellow is the 'r').
+        assert_eq!(
+            compute_pattern("eerie", "regal"),
+            vec![Gray, Green, Yellow, Gray, Gray]
+        );
+    }
+
+    #[test]
+    fn entropy_test() {
+        let candidates = vec![
+            "abide".to_string(),
+            "abled".to_string(),
+            "abode".to_string(),
+        ];
+
+        // a guess that splits the candidates into 3 singleton buckets has
+        // maximal entropy (log2(3)) for this candidate set.
+        let buckets = pattern_buckets("abide", &candidates);
+        let e = entropy(&buckets, candidates.len() as f64);
+        assert!((e - 3f64.log2()).abs() < 1e-9);
+    }
+
+    #[test]
+    fn worst_case_bucket_test() {
+        let candidates = vec![
+            "abide".to_string(),
+            "abled".to_string(),
+            "abode".to_string(),
+        ];
+
+        // three singleton buckets: the worst case is still just 1 remaining.
+        assert_eq!(worst_case_bucket(&pattern_buckets("abide", &candidates)), 1);
+
+        // "chunk" shares no letters with any candidate, so all three land
+        // in the same all-gray bucket.
+        assert_eq!(worst_case_bucket(&pattern_buckets("chunk", &candidates)), 3);
+    }
+
+    #[test]
+    fn expected_remaining_test() {
+        let candidates = vec![
+            "abide".to_string(),
+            "abled".to_string(),
+            "abode".to_string(),
+        ];
+        let n = candidates.len() as f64;
+
+        // 3 singleton buckets of size 1 out of N=3: Σ c_p²/N = 3*(1²/3) = 1.
+        let r = expected_remaining(&pattern_buckets("abide", &candidates), n);
+        assert!((r - 1.0).abs() < 1e-9);
+
+        // one bucket holding everything: Σ c_p²/N = 3²/3 = 3.
+        let r = expected_remaining(&pattern_buckets("chunk", &candidates), n);
+        assert!((r - 3.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn word_index_query_duplicate_letters_test() {
+        let dict_path = write_temp_dict("query_dup", &["abbey", "emcee", "mammy"]);
+        let index = WordIndex::build_from_dict(&dict_path, 5).unwrap();
+        std::fs::remove_file(&dict_path).ok();
+
+        let ignores = vec!['g', 'e', 's'];
+        let different_char_positions = vec![CharPosition {
+            char: 'e',
+            position: 1,
+        }];
+        let filter = Filter::new(5, ignores, vec![], different_char_positions);
+
+        let words: Vec<&str> = index
+            .query(&filter)
+            .iter()
+            .map(|i| index.words[i].as_str())
+            .collect();
+
+        // "mammy" has no 'e' at all, so the bitset query already excludes it.
+        assert!(!words.contains(&"mammy"));
+        // "emcee" passes the query (it has an 'e' and not at position 1),
+        // even though its three e's exceed the max of one: query can't see
+        // occurrence counts, so that's left for Filter::accept to catch.
+        assert!(words.contains(&"abbey"));
+        assert!(words.contains(&"emcee"));
+
+        let accepted: Vec<&str> = words.into_iter().filter(|w| filter.accept(w)).collect();
+        assert_eq!(accepted, vec!["abbey"]);
+    }
+
+    #[test]
+    fn word_index_save_load_round_trip_test() {
+        let dict_path = write_temp_dict("save_load", &["crate", "drive", "trace"]);
+        let index = WordIndex::build_from_dict(&dict_path, 5).unwrap();
+        std::fs::remove_file(&dict_path).ok();
+
+        let index_path = std::env::temp_dir().join(format!(
+            "wordle_test_index_{}_{:?}.idx",
+            std::process::id(),
+            std::thread::current().id()
+        ));
+        index.save(&index_path).unwrap();
+        let loaded = WordIndex::load(&index_path).unwrap();
+        std::fs::remove_file(&index_path).ok();
+
+        assert_eq!(loaded.length, index.length);
+        assert_eq!(loaded.words, index.words);
+
+        let filter = Filter::new(
+            5,
+            vec![],
+            vec![CharPosition {
+                char: 'c',
+                position: 0,
+            }],
+            vec![],
+        );
+        let original: Vec<usize> = index.query(&filter).iter().collect();
+        let reloaded: Vec<usize> = loaded.query(&filter).iter().collect();
+        assert_eq!(original, reloaded);
+    }
 
-    if target.len() != 5 {
-        return ret;
+    #[test]
+    fn word_index_load_truncated_file_test() {
+        let path = std::env::temp_dir().join(format!(
+            "wordle_test_index_truncated_{}_{:?}.idx",
+            std::process::id(),
+            std::thread::current().id()
+        ));
+        // Claims 3 words but the file ends before any are written.
+        std::fs::write(&path, "5\n3\n").unwrap();
+
+        let result = WordIndex::load(&path);
+        std::fs::remove_file(&path).ok();
+
+        assert_eq!(
+            result.unwrap_err().kind(),
+            std::io::ErrorKind::UnexpectedEof
+        );
+    }
+
+    #[test]
+    fn word_index_load_invalid_header_test() {
+        let path = std::env::temp_dir().join(format!(
+            "wordle_test_index_invalid_{}_{:?}.idx",
+            std::process::id(),
+            std::thread::current().id()
+        ));
+        std::fs::write(&path, "not-a-number\n").unwrap();
+
+        let result = WordIndex::load(&path);
+        std::fs::remove_file(&path).ok();
+
+        assert_eq!(result.unwrap_err().kind(), std::io::ErrorKind::InvalidData);
+    }
+}
+
+fn parse_char_position(target: String, length: usize) -> Result<Vec<CharPosition>, String> {
+    if target.len() != length {
+        return Err(format!(
+            "target \"{}\" has length {}, expected {}",
+            target,
+            target.len(),
+            length
+        ));
     }
+
+    let mut ret = Vec::new();
     for (pos, c) in target.as_str().char_indices() {
         if c == '*' {
             continue;
@@ -222,19 +710,27 @@ fn parse_char_position(target: String) -> Vec<CharPosition> {
         });
     }
 
-    ret
+    Ok(ret)
 }
 
 fn parse_ignore_chars(ignore_chars: String) -> Vec<char> {
     ignore_chars.chars().collect()
 }
 
-fn parse_different_positions(targets: Vec<String>) -> Vec<CharPosition> {
+fn parse_different_positions(
+    targets: Vec<String>,
+    length: usize,
+) -> Result<Vec<CharPosition>, String> {
     let mut ret = Vec::new();
 
     for target in targets.iter() {
-        if target.len() != 5 {
-            continue;
+        if target.len() != length {
+            return Err(format!(
+                "target \"{}\" has length {}, expected {}",
+                target,
+                target.len(),
+                length
+            ));
         }
 
         for (pos, c) in target.as_str().char_indices() {
@@ -248,7 +744,15 @@ fn parse_different_positions(targets: Vec<String>) -> Vec<CharPosition> {
         }
     }
 
-    ret
+    Ok(ret)
+}
+
+// unwrap_or_exit prints a parse error and exits instead of propagating a Result.
+fn unwrap_or_exit<T>(result: Result<T, String>) -> T {
+    result.unwrap_or_else(|e| {
+        eprintln!("{}", e);
+        std::process::exit(1);
+    })
 }
 
 #[derive(Debug, Eq, PartialEq)]
@@ -301,7 +805,7 @@ impl CharFreq {
         let re = Regex::new(r"^([a-z]):(\d+)$").unwrap();
 
         let mut char_freq = Self::new();
-        for line in lines.flatten() {
+        for line in lines.map_while(Result::ok) {
             for cap in re.captures_iter(line.as_str()) {
                 let char = cap.get(1).map(|c| c.as_str()).expect("invalid format");
                 let count = cap
@@ -368,6 +872,110 @@ impl<'a> Ord for WordScore<'a> {
     }
 }
 
+// GuessScore ranks a guess by entropy, breaking ties with WordScore's CharFreq score.
+#[derive(Debug)]
+struct GuessScore {
+    word: String,
+    entropy: f64,
+    freq_score: usize,
+}
+
+impl GuessScore {
+    fn new(word: String, entropy: f64, char_freq: &CharFreq) -> Self {
+        let freq_score = WordScore::new(word.clone(), char_freq).score;
+        Self {
+            word,
+            entropy,
+            freq_score,
+        }
+    }
+}
+
+impl PartialEq for GuessScore {
+    fn eq(&self, other: &Self) -> bool {
+        self.word == other.word
+    }
+}
+
+impl Eq for GuessScore {}
+
+impl PartialOrd for GuessScore {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for GuessScore {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.entropy
+            .partial_cmp(&other.entropy)
+            .unwrap_or(Ordering::Equal)
+            .then_with(|| self.freq_score.cmp(&other.freq_score))
+            .then_with(|| self.word.cmp(&other.word).reverse())
+    }
+}
+
+// MinimaxScore ranks a guess by its worst-case bucket (smaller is better),
+// breaking ties with entropy and then WordScore's CharFreq score.
+#[derive(Debug)]
+struct MinimaxScore {
+    word: String,
+    worst_case: usize,
+    entropy: f64,
+    expected_remaining: f64,
+    freq_score: usize,
+}
+
+impl MinimaxScore {
+    fn new(
+        word: String,
+        worst_case: usize,
+        entropy: f64,
+        expected_remaining: f64,
+        char_freq: &CharFreq,
+    ) -> Self {
+        let freq_score = WordScore::new(word.clone(), char_freq).score;
+        Self {
+            word,
+            worst_case,
+            entropy,
+            expected_remaining,
+            freq_score,
+        }
+    }
+}
+
+impl PartialEq for MinimaxScore {
+    fn eq(&self, other: &Self) -> bool {
+        self.word == other.word
+    }
+}
+
+impl Eq for MinimaxScore {}
+
+impl PartialOrd for MinimaxScore {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for MinimaxScore {
+    fn cmp(&self, other: &Self) -> Ordering {
+        // Reversed: a smaller worst_case should sort as "greater" so the
+        // max-heap in Commands::Suggest pops the safest guesses first.
+        other
+            .worst_case
+            .cmp(&self.worst_case)
+            .then_with(|| {
+                self.entropy
+                    .partial_cmp(&other.entropy)
+                    .unwrap_or(Ordering::Equal)
+            })
+            .then_with(|| self.freq_score.cmp(&other.freq_score))
+            .then_with(|| self.word.cmp(&other.word).reverse())
+    }
+}
+
 #[derive(Parser)]
 #[clap(author, version, about, long_about = None)]
 #[clap(propagate_version = true)]
@@ -389,8 +997,39 @@ enum Commands {
 
         #[clap(short, long)]
         score_sort: Option<usize>,
+
+        // With --score-sort, rank by smallest worst-case bucket (guaranteed
+        // progress) instead of by CharFreq score.
+        #[clap(long)]
+        minimax: bool,
+
+        #[clap(short, long, default_value_t = 5)]
+        length: usize,
+    },
+    Analyse {
+        #[clap(short, long, default_value_t = 5)]
+        length: usize,
+    },
+    Suggest {
+        target: Option<String>,
+
+        #[clap(short, long)]
+        ignore_chars: Option<String>,
+
+        #[clap(short, long)]
+        different_positions: Option<Vec<String>>,
+
+        #[clap(short = 'k', long)]
+        top: Option<usize>,
+
+        // Rank by smallest worst-case bucket (guaranteed progress) instead
+        // of by entropy (average-case information gain).
+        #[clap(long)]
+        minimax: bool,
+
+        #[clap(short, long, default_value_t = 5)]
+        length: usize,
     },
-    Analyse {},
 }
 
 fn main() -> std::io::Result<()> {
@@ -404,6 +1043,14 @@ fn main() -> std::io::Result<()> {
             .into_string()
             .unwrap()
     });
+    let word_index_path = env::var("WORD_INDEX_PATH").unwrap_or_else(|_| {
+        let current = env::current_dir().unwrap_or_else(|_| "/tmp".into());
+        current
+            .join("word.index")
+            .into_os_string()
+            .into_string()
+            .unwrap()
+    });
 
     match &cli.command {
         Commands::Grep {
@@ -411,9 +1058,11 @@ fn main() -> std::io::Result<()> {
             ignore_chars,
             different_positions,
             score_sort,
+            minimax,
+            length,
         } => {
             let char_position = match target {
-                Some(t) => parse_char_position(t.to_string()),
+                Some(t) => unwrap_or_exit(parse_char_position(t.to_string(), *length)),
                 None => Vec::new(),
             };
             let ignore_chars = match ignore_chars {
@@ -421,13 +1070,32 @@ fn main() -> std::io::Result<()> {
                 None => Vec::new(),
             };
             let not_match_char_position = match different_positions {
-                Some(t) => parse_different_positions(t.clone()),
+                Some(t) => unwrap_or_exit(parse_different_positions(t.clone(), *length)),
                 None => Vec::new(),
             };
-            let filter = Filter::new(ignore_chars, char_position, not_match_char_position);
-
-            let file = File::open(dict_path)?;
-            let lines = BufReader::new(file).lines();
+            let filter = Filter::new(*length, ignore_chars, char_position, not_match_char_position);
+
+            // If a word.index built for this same length exists, narrow the
+            // dictionary down via bitset algebra first; otherwise fall back
+            // to scanning the dictionary file line by line.
+            let index = WordIndex::load(&word_index_path)
+                .ok()
+                .filter(|idx| idx.length == *length);
+
+            let words: Box<dyn Iterator<Item = String>> = match &index {
+                Some(idx) => Box::new(
+                    idx.query(&filter)
+                        .iter()
+                        .map(|i| idx.words[i].clone())
+                        .collect::<Vec<_>>()
+                        .into_iter(),
+                ),
+                None => {
+                    let file = File::open(dict_path)?;
+                    let lines = BufReader::new(file).lines();
+                    Box::new(lines.map_while(Result::ok))
+                }
+            };
 
             let out = stdout();
             let mut out = BufWriter::new(out.lock());
@@ -437,56 +1105,175 @@ fn main() -> std::io::Result<()> {
                     let char_freq = CharFreq::from_file(char_freq_path);
                     let mut k = *k as isize;
 
-                    let mut heap = BinaryHeap::with_capacity(k as usize);
-                    for line in lines {
-                        match line {
-                            Ok(line) if filter.accept(line.to_lowercase().as_str()) => {
-                                heap.push(WordScore::new(line, &char_freq));
-                            }
-                            _ => continue,
+                    if *minimax {
+                        let candidates: Vec<String> =
+                            words.filter(|w| filter.accept(w.to_lowercase().as_str())).collect();
+                        let lower: Vec<String> =
+                            candidates.iter().map(|w| w.to_lowercase()).collect();
+                        let n = lower.len() as f64;
+
+                        let mut heap = BinaryHeap::with_capacity(k as usize);
+                        for (word, guess) in candidates.iter().zip(lower.iter()) {
+                            let buckets = pattern_buckets(guess, &lower);
+                            let worst_case = worst_case_bucket(&buckets);
+                            let e = entropy(&buckets, n);
+                            let remaining = expected_remaining(&buckets, n);
+                            heap.push(MinimaxScore::new(
+                                word.clone(),
+                                worst_case,
+                                e,
+                                remaining,
+                                &char_freq,
+                            ));
                         }
-                    }
 
-                    while let Some(ws) = heap.pop() {
-                        if k <= 0 {
-                            break;
+                        while let Some(ms) = heap.pop() {
+                            if k <= 0 {
+                                break;
+                            }
+                            k -= 1;
+
+                            println!("{}\t{}\t{:.4}", ms.word, ms.worst_case, ms.expected_remaining);
+                        }
+                    } else {
+                        let mut heap = BinaryHeap::with_capacity(k as usize);
+                        for word in words {
+                            if filter.accept(word.to_lowercase().as_str()) {
+                                heap.push(WordScore::new(word, &char_freq));
+                            }
                         }
-                        k -= 1;
 
-                        println!("{}", ws.word);
+                        while let Some(ws) = heap.pop() {
+                            if k <= 0 {
+                                break;
+                            }
+                            k -= 1;
+
+                            println!("{}", ws.word);
+                        }
                     }
                 }
                 None => {
-                    for line in lines {
-                        match line {
-                            Ok(line) if filter.accept(line.to_lowercase().as_str()) => {
-                                out.write_all(line.as_bytes())?;
-                                out.write_all(b"\n")?;
-                            }
-                            _ => continue,
+                    for word in words {
+                        if filter.accept(word.to_lowercase().as_str()) {
+                            out.write_all(word.as_bytes())?;
+                            out.write_all(b"\n")?;
                         }
                     }
                 }
             }
         }
-        Commands::Analyse {} => {
-            let mut file = File::open(dict_path)?;
-            let mut buffer = String::new();
-            file.read_to_string(&mut buffer)?;
+        Commands::Analyse { length } => {
+            let file = File::open(&dict_path)?;
+            let lines = BufReader::new(file).lines();
 
             let mut char_freq = CharFreq::new();
-            for c in buffer.chars() {
-                char_freq.add_char(c);
+            for line in lines {
+                match line {
+                    Ok(line) if line.len() == *length => {
+                        for c in line.chars() {
+                            char_freq.add_char(c);
+                        }
+                    }
+                    _ => continue,
+                }
             }
 
             let out = File::options()
                 .create(true)
+                .truncate(true)
                 .write(true)
                 .open(char_freq_path)?;
             let mut out = BufWriter::new(out);
             for (c, count) in char_freq.to_vec() {
                 out.write_all(format!("{}:{}\n", c, count).as_bytes())?;
             }
+
+            let index = WordIndex::build_from_dict(&dict_path, *length)?;
+            index.save(&word_index_path)?;
+        }
+        Commands::Suggest {
+            target,
+            ignore_chars,
+            different_positions,
+            top,
+            minimax,
+            length,
+        } => {
+            let char_position = match target {
+                Some(t) => unwrap_or_exit(parse_char_position(t.to_string(), *length)),
+                None => Vec::new(),
+            };
+            let ignore_chars = match ignore_chars {
+                Some(t) => parse_ignore_chars(t.to_string()),
+                None => Vec::new(),
+            };
+            let not_match_char_position = match different_positions {
+                Some(t) => unwrap_or_exit(parse_different_positions(t.clone(), *length)),
+                None => Vec::new(),
+            };
+            let filter = Filter::new(*length, ignore_chars, char_position, not_match_char_position);
+
+            let file = File::open(dict_path)?;
+            let lines = BufReader::new(file).lines();
+
+            let mut candidates = Vec::new();
+            for line in lines {
+                match line {
+                    Ok(line) if filter.accept(line.to_lowercase().as_str()) => {
+                        candidates.push(line.to_lowercase());
+                    }
+                    _ => continue,
+                }
+            }
+
+            let char_freq = CharFreq::from_file(char_freq_path);
+            let k = top.unwrap_or(10);
+
+            let n = candidates.len() as f64;
+
+            if *minimax {
+                let mut heap = BinaryHeap::with_capacity(k);
+                for guess in candidates.iter() {
+                    let buckets = pattern_buckets(guess, &candidates);
+                    let worst_case = worst_case_bucket(&buckets);
+                    let e = entropy(&buckets, n);
+                    let remaining = expected_remaining(&buckets, n);
+                    heap.push(MinimaxScore::new(
+                        guess.clone(),
+                        worst_case,
+                        e,
+                        remaining,
+                        &char_freq,
+                    ));
+                }
+
+                let mut k = k as isize;
+                while let Some(ms) = heap.pop() {
+                    if k <= 0 {
+                        break;
+                    }
+                    k -= 1;
+
+                    println!("{}\t{}\t{:.4}", ms.word, ms.worst_case, ms.expected_remaining);
+                }
+            } else {
+                let mut heap = BinaryHeap::with_capacity(k);
+                for guess in candidates.iter() {
+                    let e = entropy(&pattern_buckets(guess, &candidates), n);
+                    heap.push(GuessScore::new(guess.clone(), e, &char_freq));
+                }
+
+                let mut k = k as isize;
+                while let Some(gs) = heap.pop() {
+                    if k <= 0 {
+                        break;
+                    }
+                    k -= 1;
+
+                    println!("{}\t{:.4}", gs.word, gs.entropy);
+                }
+            }
         }
     }
 